@@ -5,6 +5,7 @@ use crate::TreeDegree;
 
 /// Represents a codeword.
 #[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Code {
     /// Concatenated fragments of the codeword.
     /// 
@@ -69,6 +70,12 @@ impl Code {
         })
     }
 
+    /// Returns whether (unreversed) `self` is a prefix of (unreversed) `other`.
+    /// Each code is a prefix of itself.
+    pub fn is_prefix_of(&self, other: &Code, degree: impl TreeDegree) -> bool {
+        self.len <= other.len && other.iter(degree).take(self.len as usize).eq(self.iter(degree))
+    }
+
     /// Returns whether `self` consists of zero fragments.
     #[inline] pub fn is_empty(&self) -> bool { self.len == 0 }
 
@@ -83,6 +90,18 @@ impl Code {
     }
 }
 
+/// Checks whether none of the (unreversed) `codes` is a prefix of another one.
+/// Equal codes are considered to be prefixes of each other.
+///
+/// The algorithm sorts `codes` lexicographically (by their fragments)
+/// and then compares only the adjacent ones.
+/// Each comparison walks the fragments of the codes, so the algorithm runs in *O(L n log n)* time,
+/// where *n* is the number of codes and *L* is the length of the longest one.
+pub fn is_prefix_free<D: TreeDegree>(codes: &mut [Code], degree: D) -> bool {
+    codes.sort_unstable_by(|a, b| a.iter(degree).cmp(b.iter(degree)));
+    codes.windows(2).all(|pair| !pair[0].is_prefix_of(&pair[1], degree))
+}
+
 /// Iterator over the fragments of (unreversed) code.
 pub struct CodeIterator<D: TreeDegree> {
     code: Code,
@@ -199,4 +218,19 @@ mod tests {
         assert_eq!(code.len, 0);
         assert_eq!(code.extract_first(Degree(3)), None);
     }
+
+    #[test]
+    fn prefix_free() {
+        let mut codes = [Code { content: 0b1, len: 1 }, Code { content: 0b00, len: 2 }, Code { content: 0b01, len: 2 }];
+        assert!(is_prefix_free(&mut codes, BitsPerFragment(1)));
+        let mut codes = [Code { content: 0b11, len: 2 }, Code { content: 0b0, len: 1 }, Code { content: 0b110, len: 3 }];
+        assert!(Code { content: 0b11, len: 2 }.is_prefix_of(&Code { content: 0b110, len: 3 }, BitsPerFragment(1)));
+        assert!(!is_prefix_free(&mut codes, BitsPerFragment(1)));
+        let mut codes = [Code { content: 0b01, len: 2 }, Code { content: 0b01, len: 2 }];
+        assert!(!is_prefix_free(&mut codes, BitsPerFragment(1)));
+        let mut codes = [Code { content: 2, len: 1 }, Code { content: 3+1, len: 2 }, Code { content: 9+2*3, len: 3 }];
+        assert!(is_prefix_free(&mut codes, Degree(3)));
+        let mut codes = [Code { content: 2, len: 1 }, Code { content: 3+1, len: 2 }, Code { content: 9+3, len: 3 }];
+        assert!(!is_prefix_free(&mut codes, Degree(3)));
+    }
 }
\ No newline at end of file
@@ -63,6 +63,10 @@ where
     /// - or [`DecodingResult::Invalid`] if the codeword is invalid (possible only for bits per fragment > 1).
     ///
     /// Result is undefined if `fragment` exceeds `tree_degree`.
+    ///
+    /// The code of the only value of a [degenerate](Coding::is_degenerate) `coding` is empty,
+    /// so no fragment should be consumed to decode it. Therefore, the caller must check this case first
+    /// (methods that decode from many fragments, like [`Self::decode`], do this).
     pub fn consume(
         &mut self,
         coding: &'huff Coding<ValueType, D>,
@@ -92,6 +96,9 @@ where
     /// - an [`DecodingResult::Incomplete`] if the codeword is incomplete and the next fragment is needed;
     /// - or [`DecodingResult::Invalid`] if the codeword is invalid (possible only for `degree` greater than 2)
     ///     or `fragment` is not less than `degree`.
+    ///
+    /// Like in the case of [`Self::consume`], a [degenerate](Coding::is_degenerate) `coding`
+    /// must be checked by the caller first.
     #[inline(always)]
     pub fn consume_checked(
         &mut self,
@@ -111,6 +118,8 @@ where
     /// To decode the next value, self must be [reset](Self::reset) first
     /// (see also [Self::decode_next]).
     ///
+    /// If `coding` consists of a single value, it is returned without consuming any fragment.
+    ///
     /// In case of failure, returns:
     /// - [`DecodingResult::Incomplete`] if the iterator exhausted before the value was decoded
     ///   ([`Self::consumed_fragments`] enables checking if the iterator yielded any fragment before exhausting).
@@ -121,6 +130,9 @@ where
         coding: &'huff Coding<ValueType, D>,
         fragments: &mut I,
     ) -> DecodingResult<&'huff ValueType> {
+        if coding.is_degenerate() {
+            return DecodingResult::Value(&coding.values[0]);
+        }
         while let Some(fragment) = fragments.next() {
             match self.consume(coding, fragment.into()) {
                 DecodingResult::Incomplete => {}
@@ -138,6 +150,9 @@ where
         coding: &'huff Coding<ValueType, D>,
         fragments: &[F],
     ) -> DecodingResult<&'huff ValueType> {
+        if coding.is_degenerate() {
+            return DecodingResult::Value(&coding.values[0]);
+        }
         // Use an index to manually iterate over the vector
         let mut index = 0;
         while index < fragments.len() {
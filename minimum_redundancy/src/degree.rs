@@ -20,6 +20,7 @@ pub trait TreeDegree: Sized + Copy + Mul<u32, Output=u32> {
     }
 
     /// Reads `Self` from `input`.
+    /// Returns [`std::io::ErrorKind::InvalidData`] error if the degree read is invalid.
     fn read(input: &mut dyn std::io::Read) -> std::io::Result<Self>;
 
     /// Returns the `fragment_nr`-th fragment of `bits`. Result is less than `self.tree_degree()`.
@@ -67,6 +68,7 @@ pub trait TreeDegree: Sized + Copy + Mul<u32, Output=u32> {
 /// It is faster than `Degree` and should be preferred
 /// for degrees that are the powers of two.
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(try_from = "u8", into = "u8"))]
 pub struct BitsPerFragment(pub u8);
 
 impl Mul<u32> for BitsPerFragment {
@@ -89,7 +91,8 @@ impl TreeDegree for BitsPerFragment {
     }
 
     fn read(input: &mut dyn std::io::Read) -> std::io::Result<Self> {
-        AsIs::read(input).map(|v| Self(v))
+        let bits: u8 = AsIs::read(input)?;
+        Self::try_from(bits).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
     }
 
     fn get_fragment(&self, bits: u32, fragment_nr: u32) -> u32 {
@@ -112,7 +115,7 @@ impl TreeDegree for BitsPerFragment {
     #[inline] fn reverse_code(&self, mut bits: u32, len: u32) -> u32 {
         //TODO faster code for self.0 == 1 or self.0 being power of 2
         if self.0 == 1 {    // very common and the slowest case
-            return bits.reverse_bits() >> 32u32.saturating_sub(len);
+            return bits.reverse_bits().checked_shr(32u32.saturating_sub(len)).unwrap_or(0);
         }
         let mut len = len.min(self.code_capacity() as u32) as u8;
         let mask = self.as_u32() - 1;
@@ -137,6 +140,22 @@ impl TreeDegree for BitsPerFragment {
     }*/
 }
 
+impl TryFrom<u8> for BitsPerFragment {
+    type Error = &'static str;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if (1..32).contains(&value) {
+            Ok(Self(value))
+        } else {
+            Err("BitsPerFragment requires the number of bits to be in the range [1, 31]")
+        }
+    }
+}
+
+impl From<BitsPerFragment> for u8 {
+    #[inline] fn from(value: BitsPerFragment) -> Self { value.0 }
+}
+
 impl TryFrom<Degree> for BitsPerFragment {
     type Error = &'static str;
 
@@ -153,8 +172,25 @@ impl TryFrom<Degree> for BitsPerFragment {
 /// It is slower than `BitsPerFragment` and should be avoided
 /// when the degree is the power of two.
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(try_from = "u32", into = "u32"))]
 pub struct Degree(pub u32);
 
+impl TryFrom<u32> for Degree {
+    type Error = &'static str;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        if value >= 2 {
+            Ok(Self(value))
+        } else {
+            Err("Degree of the tree must be at least 2")
+        }
+    }
+}
+
+impl From<Degree> for u32 {
+    #[inline] fn from(value: Degree) -> Self { value.0 }
+}
+
 impl Mul<u32> for Degree {
     type Output = u32;
 
@@ -169,7 +205,8 @@ impl TreeDegree for Degree {
     }
 
     fn read(input: &mut dyn std::io::Read) -> std::io::Result<Self> {
-        VByte::read(input).map(|v| Self(v))
+        let degree: u32 = VByte::read(input)?;
+        Self::try_from(degree).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
     }
 
     #[inline] fn get_fragment(&self, bits: u32, fragment_nr: u32) -> u32 {
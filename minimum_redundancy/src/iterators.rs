@@ -7,6 +7,8 @@ use std::iter::FusedIterator;
 /// - values assigned to the leafs at the current level,
 /// - number of internal nodes at the level, which equals the bits of codeword assigned to the first leaf at the level,
 /// - index of the level in the tree, which equals to the length of the codewords assigned to leafs at the level.
+///
+/// For coding of a single value, only one level, of index 0, is exposed.
 #[derive(Copy, Clone)]
 pub struct LevelIterator<'coding, ValueType, D>
 where
@@ -52,7 +54,11 @@ where
     D: Clone,
 {
     fn len(&self) -> usize {
-        self.coding.internal_nodes_count.len() - self.level as usize
+        if self.last_value_index == self.coding.values.len() {
+            0
+        } else {
+            self.coding.internal_nodes_count.len() - self.level as usize
+        }
     }
 }
 
@@ -65,6 +71,10 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         (self.last_value_index != self.coding.values.len()).then(|| {
+            if self.coding.is_degenerate() {
+                self.last_value_index = 1;
+                return (&self.coding.values[..], 0, 0);
+            }
             let value_index = self.last_value_index;
             let internal_nodes = self.coding.internal_nodes_count[self.level as usize];
             self.level += 1;
@@ -10,7 +10,7 @@ use dyn_size_of::GetSize;
 use std::borrow::Borrow;
 
 mod code;
-pub use code::{Code, CodeIterator, ReversedCodeIterator};
+pub use code::{Code, CodeIterator, ReversedCodeIterator, is_prefix_free};
 
 mod frequencies;
 pub use frequencies::Frequencies;
@@ -22,8 +22,13 @@ mod iterators;
 pub use iterators::{CodesIterator, LevelIterator, ReversedCodesIterator};
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 /// Succinct representation of minimum-redundancy coding
 /// (huffman tree of some degree in the canonical form).
+///
+/// With the `serde` feature enabled, `Coding` can be serialized and deserialized by serde.
+/// Deserialization checks whether `internal_nodes_count` is consistent
+/// with the number of `values` and `degree`, and fails with a descriptive error if not.
 pub struct Coding<ValueType, D = BitsPerFragment>
 where
     ValueType: Clone, // Ensure ValueType supports cloning
@@ -33,11 +38,39 @@ where
     pub values: Box<[ValueType]>,
     /// Number of the internal nodes of each tree level. The root is not counted.
     /// Contains exactly one zero at the end.
+    ///
+    /// The tree of a coding of a single value is degenerate (see [`Self::is_degenerate`]):
+    /// `internal_nodes_count` is `[0]`, but the root is a leaf and the value is assigned an empty code.
+    /// For any other number of values, `[0]` describes a tree of one level below the root.
     pub internal_nodes_count: Box<[u32]>,
     /// Size of the fragment given as bits per fragment or the degree of the Huffman tree.
     pub degree: D,
 }
 
+/// Fields of [`Coding`], deserialized before validating their consistency.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "Coding")]
+struct CodingParts<ValueType, D> {
+    values: Box<[ValueType]>,
+    internal_nodes_count: Box<[u32]>,
+    degree: D,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, ValueType, D> serde::Deserialize<'de> for Coding<ValueType, D>
+where
+    ValueType: Clone + serde::Deserialize<'de>,
+    D: TreeDegree + serde::Deserialize<'de>,
+{
+    fn deserialize<De: serde::Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let CodingParts { values, internal_nodes_count, degree } = CodingParts::deserialize(deserializer)?;
+        let result = Self { values, internal_nodes_count, degree };
+        result.check_structure().map_err(serde::de::Error::custom)?;
+        Ok(result)
+    }
+}
+
 /// Points the number of bytes needed to store value of the type `ValueType`.
 /// This number of bytes can be constant or can depend on the value.
 pub enum ValueSize<'v, ValueType> {
@@ -71,6 +104,10 @@ where
 
     /// Constructs coding for given `frequencies` of values and `degree` of the Huffman tree.
     /// Values are cloned from `frequencies`.
+    ///
+    /// If `frequencies` contains exactly one value, the Huffman tree is degenerate
+    /// (its root is a leaf) and the value is assigned an empty code (of zero fragments).
+    /// Encoding such a value produces no fragments, and decoding returns it without consuming any.
     pub fn from_frequencies_cloned<F: Frequencies<Value = ValueType>>(
         degree: D,
         frequencies: &F,
@@ -106,6 +143,43 @@ where
             .sum()
     }
 
+    /// Checks whether none of the codewords assigned by `self` is a prefix of another one.
+    ///
+    /// The algorithm runs in *O(L n log n)* time and *O(n)* memory,
+    /// where *n* is the number of values and
+    /// *L* is the number of fragments in the longest codeword.
+    pub fn is_prefix_free(&self) -> bool {
+        let mut codes: Box<[Code]> = self.codes().map(|(_, c)| c).collect();
+        is_prefix_free(&mut codes, self.degree)
+    }
+
+    /// Checks whether the codewords assigned by `self` satisfy the Kraft inequality with equality,
+    /// i.e. each internal node of the tree has exactly `degree` children.
+    /// In the canonical form, only the last level of the tree can have unused indices,
+    /// so it is enough to check whether values are assigned to all leaves of this level.
+    ///
+    /// Note that codings of degree greater than 2 are often incomplete.
+    ///
+    /// The algorithm runs in *O(L)* time and *O(1)* memory,
+    /// where *L* is the number of fragments in the longest codeword.
+    pub fn is_complete(&self) -> bool {
+        if self.is_degenerate() { return true; }
+        let Some((last_level_values, _, level)) = self.levels().last() else { return false; };
+        let last_level_size = if level == 1 {
+            self.degree.as_u32()
+        } else {
+            self.degree * self.internal_nodes_count[level as usize - 2]
+        };
+        last_level_values.len() == last_level_size as usize
+    }
+
+    /// Returns whether the tree is degenerate, i.e. `self` is a coding of a single value,
+    /// which is assigned an empty code (of zero fragments).
+    #[inline(always)]
+    pub fn is_degenerate(&self) -> bool {
+        self.values.len() == 1
+    }
+
     /// Returns decoder that allows for decoding a value.
     #[inline]
     pub fn decoder(&self) -> Decoder<ValueType, D> {
@@ -118,6 +192,10 @@ where
     ///
     /// The algorithm runs in *O(values.len)* time,
     /// in-place (it uses and changes `freq` and move values to the returned `Coding` object).
+    ///
+    /// A single value is assigned an empty code (see [`Self::from_frequencies_cloned`]).
+    /// Note that earlier versions of the library assigned it a code of one fragment,
+    /// so data of a single value encoded by these versions cannot be correctly decoded.
     pub fn from_sorted<W>(degree: D, mut values: Box<[ValueType]>, freq: &mut [W]) -> Self
     where
         W: Weight,
@@ -214,6 +292,91 @@ where
         Self::from_sorted(degree, values, freq)
     }
 
+    /// Constructs coding for given `frequencies` of values and `degree` of the Huffman tree,
+    /// whose codewords consist of at most `max_len` fragments.
+    ///
+    /// See [`Self::from_sorted_limited`] for details.
+    pub fn from_frequencies_limited<F: Frequencies<Value = ValueType>>(degree: D, frequencies: F, max_len: u32) -> Self {
+        let (values, freq) = frequencies.into_sorted();
+        Self::from_sorted_limited(degree, values, &freq, max_len)
+    }
+
+    /// Construct coding (of given `degree`) for the given `values`,
+    /// whose codewords consist of at most `max_len` fragments and
+    /// whose expected length is minimal under this constraint.
+    /// `freq` is an array of numbers of occurrences of corresponding values.
+    /// `freq` has to be in non-descending order and of the same length as values.
+    ///
+    /// Panics if the `degree` to the power of `max_len` is less than the number of values,
+    /// as no prefix code can satisfy the constraint then.
+    ///
+    /// It uses the package-merge algorithm (L.L. Larmore, D.S. Hirschberg,
+    /// *A fast algorithm for optimal length-limited Huffman codes*, 1990),
+    /// generalized to trees of any degree by packaging `degree` items at a time.
+    /// The algorithm runs in *O(values.len * L)* time and memory,
+    /// where *L* is the minimum of `max_len` and `values.len`.
+    pub fn from_sorted_limited<W>(degree: D, mut values: Box<[ValueType]>, freq: &[W], max_len: u32) -> Self
+    where
+        W: Weight,
+    {
+        let len = freq.len();
+        let tree_degree = degree.as_u32() as usize;
+        assert!((tree_degree as u64).checked_pow(max_len).is_none_or(|capacity| capacity >= len as u64),
+            "minimum_redundancy::Coding cannot assign codes not longer than max_len to all values");
+        if len <= tree_degree {
+            return Self::from_sorted(degree, values, &mut freq.to_vec());
+        }
+        // here: len > tree_degree >= 2 and max_len >= 2
+        let max_len = max_len.min(len as u32 - 1) as usize;  // optimal codes are never longer
+        // dummy leaves of weight 0 make each internal node of the tree have exactly tree_degree children:
+        let dummies = (tree_degree - 2) - (len - 2) % (tree_degree - 1);
+        let leaves: Vec<W> = std::iter::repeat_n(W::of(0), dummies).chain(freq.iter().copied()).collect();
+
+        // is_package[k][i] shows whether i-th item of the list constructed for level max_len-k is a package
+        let mut is_package = vec![vec![false; leaves.len()].into_boxed_slice()];
+        let mut list = leaves.clone();  // weights of items of the list for the deepest level
+        for _ in 1..max_len {
+            let packages: Vec<W> = list.chunks_exact(tree_degree)
+                .map(|items| items[1..].iter().fold(items[0], |sum, w| sum + *w))
+                .collect();
+            let mut merged = Vec::with_capacity(leaves.len() + packages.len());
+            let mut merged_is_package = Vec::with_capacity(leaves.len() + packages.len());
+            let (mut l, mut p) = (0, 0);
+            while l < leaves.len() || p < packages.len() {
+                if p == packages.len() || (l < leaves.len() && leaves[l] <= packages[p]) {
+                    merged.push(leaves[l]);
+                    merged_is_package.push(false);
+                    l += 1;
+                } else {
+                    merged.push(packages[p]);
+                    merged_is_package.push(true);
+                    p += 1;
+                }
+            }
+            list = merged;
+            is_package.push(merged_is_package.into_boxed_slice());
+        }
+
+        // each selected leaf (at any level) lengthens the code of its value by one fragment
+        let mut lengths = vec![0u32; leaves.len()];  // in the order of leaves
+        let mut selected = tree_degree * (leaves.len() - 1) / (tree_degree - 1);
+        for level_is_package in is_package.iter().rev() {
+            let packages = level_is_package[..selected].iter().filter(|p| **p).count();
+            for length in &mut lengths[..selected - packages] { *length += 1; }
+            selected = tree_degree * packages;
+        }
+
+        let mut values_count = vec![0u32; lengths[dummies] as usize];
+        for length in &lengths[dummies..] { values_count[*length as usize - 1] += 1; }
+        values.reverse();
+        Self {
+            values,
+            internal_nodes_count: Self::internal_nodes_count_for(degree.as_u32(), len, &values_count)
+                .expect("package-merge should construct a valid coding"),
+            degree,
+        }
+    }
+
     /// Returns number of bytes which `write_internal_nodes_count` will write.
     pub fn write_internal_nodes_count_bytes(&self) -> usize {
         VByte::array_size(&self.internal_nodes_count[..self.internal_nodes_count.len() - 1])
@@ -315,6 +478,120 @@ where
         })
     }
 
+    /// Returns number of bytes which `write_lengths` will write,
+    /// assuming that each call to `write_value` writes the number of bytes pointed by `value_size`.
+    pub fn write_lengths_size_bytes(&self, value_size: ValueSize<ValueType>) -> usize {
+        self.degree.write_size_bytes()
+            + self.write_values_size_bytes(value_size)
+            + VByte::size(self.levels().len())
+            + self.levels().map(|(values, _, _)| VByte::size(values.len())).sum::<usize>()
+    }
+
+    /// Writes `self` to the given `output`, using `write_value` to write each value,
+    /// in the form of canonical code lengths.
+    ///
+    /// The following are written: the degree, the values (by [`Self::write_values`]) in canonical order
+    /// (i.e. by non-decreasing lengths of their codes), and, as VByte values,
+    /// the number of code lengths followed by the numbers of values with codes of successive lengths
+    /// (starting from 1, or from 0 for a coding of a single value).
+    /// Since the canonical coding is determined by these lengths,
+    /// [`Self::read_lengths`] reconstructs the coding that assigns exactly the same codewords.
+    pub fn write_lengths<F>(&self, output: &mut dyn std::io::Write, write_value: F) -> std::io::Result<()>
+    where
+        F: FnMut(&mut dyn std::io::Write, &ValueType) -> std::io::Result<()>,
+    {
+        self.degree.write(output)?;
+        self.write_values(output, write_value)?;
+        VByte::write(output, self.levels().len())?;
+        self.levels().try_for_each(|(values, _, _)| VByte::write(output, values.len()))
+    }
+
+    /// Reads (written by `write_lengths`) `Coding` from the given `input`, using `read_value` to read each value.
+    ///
+    /// Returns [`std::io::ErrorKind::InvalidData`] error if the degree read is invalid or
+    /// the code lengths read do not describe a canonical coding of the values read.
+    pub fn read_lengths<F>(input: &mut dyn std::io::Read, read_value: F) -> std::io::Result<Self>
+    where
+        F: FnMut(&mut dyn std::io::Read) -> std::io::Result<ValueType>,
+    {
+        let degree = D::read(input)?;
+        let values = Self::read_values(input, read_value)?;
+        let levels: usize = VByte::read(input)?;
+        if levels > values.len().max(1) {   // each level, except the last one, has an internal node with 2+ children
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData,
+                "minimum_redundancy::Coding: more code lengths than values"));
+        }
+        let mut values_count = Vec::<u32>::with_capacity(levels);
+        for _ in 0..levels {
+            values_count.push(VByte::read(input)?);
+        }
+        let internal_nodes_count = Self::internal_nodes_count_for(degree.as_u32(), values.len(), &values_count)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Ok(Self { values, internal_nodes_count, degree })
+    }
+
+    /// Returns `internal_nodes_count` of the coding of degree `tree_degree` that
+    /// for each (successive) level of the tree has the given number of leaves (values),
+    /// or an error message if there is no such coding of `total_values` values.
+    fn internal_nodes_count_for(tree_degree: u32, total_values: usize, values_count: &[u32]) -> Result<Box<[u32]>, &'static str> {
+        if tree_degree < 2 { return Err("minimum_redundancy::Coding: degree of the tree must be at least 2"); }
+        if values_count.iter().map(|c| *c as usize).sum::<usize>() != total_values {
+            return Err("minimum_redundancy::Coding: numbers of values at the levels do not sum up to the number of values");
+        }
+        if total_values <= 1 {  // the empty coding or the coding of a single value with an empty code
+            return if values_count.len() <= 1 { Ok(vec![0u32].into_boxed_slice()) } else {
+                Err("minimum_redundancy::Coding: coding of at most one value must consist of one level")
+            };
+        }
+        if values_count.last() == Some(&0) {
+            return Err("minimum_redundancy::Coding: the last level of the tree must have values");
+        }
+        let mut result = Vec::with_capacity(values_count.len());
+        let mut level_size = tree_degree;
+        for leaves in values_count {
+            let internal_nodes = level_size.checked_sub(*leaves)
+                .ok_or("minimum_redundancy::Coding: level of the tree has more values than nodes")?;
+            result.push(internal_nodes);
+            level_size = internal_nodes.checked_mul(tree_degree)
+                .ok_or("minimum_redundancy::Coding: level of the tree has more than 2 to the power of 32 nodes")?;
+        }
+        *result.last_mut().unwrap() = 0;    // no internal nodes at the last level, which can also have unused indices
+        if result[..result.len() - 1].contains(&0) {
+            return Err("minimum_redundancy::Coding: only the last level of the tree can have no internal nodes");
+        }
+        Ok(result.into_boxed_slice())
+    }
+
+    /// Returns the number of values at each level of the tree described by `internal_nodes_count`,
+    /// assigning all values that do not fit in the previous levels to the last one.
+    #[cfg(feature = "serde")]
+    fn values_count(&self) -> Box<[u32]> {
+        let Some((_, levels)) = self.internal_nodes_count.split_last() else { return Box::new([]) };
+        let tree_degree = self.degree.as_u32();
+        let mut result = Vec::with_capacity(levels.len() + 1);
+        let mut level_size = tree_degree;
+        let mut assigned = 0usize;
+        for internal_nodes in levels {
+            let leaves = level_size.saturating_sub(*internal_nodes);
+            result.push(leaves);
+            assigned += leaves as usize;
+            level_size = internal_nodes.saturating_mul(tree_degree);
+        }
+        result.push(self.values.len().saturating_sub(assigned).try_into().unwrap_or(u32::MAX));
+        result.into_boxed_slice()
+    }
+
+    /// Checks whether `internal_nodes_count` is consistent with `degree` and the number of `values`,
+    /// i.e. whether it is the same as the one `read_lengths` reconstructs from the numbers of values at the levels.
+    #[cfg(feature = "serde")]
+    fn check_structure(&self) -> Result<(), &'static str> {
+        let expected = Self::internal_nodes_count_for(self.degree.as_u32(), self.values.len(), &self.values_count())?;
+        if expected != self.internal_nodes_count {
+            return Err("minimum_redundancy::Coding: internal_nodes_count is inconsistent with the degree and the number of values");
+        }
+        Ok(())
+    }
+
     /// Returns iterator over the levels of the huffman tree.
     #[inline]
     pub fn levels(&self) -> LevelIterator<'_, ValueType, D> {
@@ -508,6 +785,269 @@ mod tests {
         assert_eq!(huffman.internal_nodes_count, read.internal_nodes_count);
     }
 
+    fn test_read_write_lengths<FS: TreeDegree>(huffman: &Coding<u8, FS>) {
+        let mut buff = Vec::new();
+        huffman.write_lengths(&mut buff, |b, v| AsIs::write(b, *v)).unwrap();
+        assert_eq!(buff.len(), huffman.write_lengths_size_bytes(ValueSize::Const(1)));
+        let read = Coding::<u8, FS>::read_lengths(&mut &buff[..], |b| AsIs::read(b)).unwrap();
+        assert_eq!(huffman.degree.as_u32(), read.degree.as_u32());
+        assert_eq!(huffman.values, read.values);
+        assert_eq!(huffman.internal_nodes_count, read.internal_nodes_count);
+        assert_eq!(huffman.codes_for_values(), read.codes_for_values());
+    }
+
+    #[test]
+    fn coding_read_write_lengths() {
+        let text = b"minimum-redundancy codes are fully determined by the lengths of their codewords";
+        test_read_write_lengths(&Coding::from_iter(BitsPerFragment(1), text));
+        test_read_write_lengths(&Coding::from_iter(BitsPerFragment(2), text));
+        test_read_write_lengths(&Coding::from_iter(Degree(4), text));
+        test_read_write_lengths(&Coding::from_iter(Degree(3), text));
+        let all_bytes: Vec<u8> = (0..=255u8).flat_map(|v| std::iter::repeat_n(v, v as usize % 7 + 1)).collect();
+        test_read_write_lengths(&Coding::from_iter(BitsPerFragment(1), &all_bytes));
+        test_read_write_lengths(&Coding::from_iter(BitsPerFragment(2), &all_bytes));
+        test_read_write_lengths(&Coding::from_iter(BitsPerFragment(1), b"aaa"));
+        test_read_write_lengths(&Coding::from_iter(BitsPerFragment(2), b""));
+        // codes longer than code_capacity
+        let mut fib = (1u64, 1u64);
+        let fibonacci: HashMap<u8, u64> = (0..50u8).map(|v| { fib = (fib.1, fib.0 + fib.1); (v, fib.0) }).collect();
+        let deep = Coding::from_frequencies(BitsPerFragment(1), fibonacci);
+        assert!(deep.levels().len() > BitsPerFragment(1).code_capacity() as usize + 1);
+        test_read_write_lengths(&deep);
+        // huge number of code lengths
+        let mut invalid = Vec::new();
+        BitsPerFragment(1).write(&mut invalid).unwrap();
+        invalid.extend_from_slice(&[2, b'a', b'b']);
+        VByte::write(&mut invalid, 1usize << 63).unwrap();
+        assert_eq!(
+            Coding::<u8>::read_lengths(&mut &invalid[..], |b| AsIs::read(b)).err().map(|e| e.kind()),
+            Some(std::io::ErrorKind::InvalidData)
+        );
+        // invalid degrees
+        for degree in [0u8, 32, 40] {
+            let invalid = [degree, 2, b'a', b'b', 1, 2];
+            assert_eq!(
+                Coding::<u8>::read_lengths(&mut &invalid[..], |b| AsIs::read(b)).err().map(|e| e.kind()),
+                Some(std::io::ErrorKind::InvalidData)
+            );
+        }
+        let invalid = [1u8, 1, b'a', 1, 1];
+        assert_eq!(
+            Coding::<u8, Degree>::read_lengths(&mut &invalid[..], |b| AsIs::read(b)).err().map(|e| e.kind()),
+            Some(std::io::ErrorKind::InvalidData)
+        );
+        // 3 values with codes of length 1 do not fit in a binary tree
+        let mut invalid = Vec::new();
+        BitsPerFragment(1).write(&mut invalid).unwrap();
+        invalid.extend_from_slice(&[3, b'a', b'b', b'c', 1, 3]);   // values, then lengths
+        assert_eq!(
+            Coding::<u8>::read_lengths(&mut &invalid[..], |b| AsIs::read(b)).err().map(|e| e.kind()),
+            Some(std::io::ErrorKind::InvalidData)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    fn test_serde<FS>(huffman: &Coding<u8, FS>)
+        where FS: TreeDegree + serde::Serialize + for<'de> serde::Deserialize<'de>
+    {
+        let check = |read: Coding<u8, FS>| {
+            assert_eq!(huffman.degree.as_u32(), read.degree.as_u32());
+            assert_eq!(huffman.values, read.values);
+            assert_eq!(huffman.internal_nodes_count, read.internal_nodes_count);
+            assert_eq!(huffman.codes_for_values(), read.codes_for_values());
+        };
+        check(bincode::deserialize(&bincode::serialize(huffman).unwrap()).unwrap());
+        check(serde_json::from_str(&serde_json::to_string(huffman).unwrap()).unwrap());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn coding_serde() {
+        test_serde(&Coding::from_iter(BitsPerFragment(1), b"serde support for minimum_redundancy"));
+        test_serde(&Coding::from_iter(Degree(3), b"serde support for minimum_redundancy"));
+        test_serde(&Coding::from_iter(BitsPerFragment(2), b"a"));
+        let all_bytes: Vec<u8> = (0..=255u8).flat_map(|v| std::iter::repeat_n(v, v as usize + 1)).collect();
+        test_serde(&Coding::from_iter(BitsPerFragment(1), &all_bytes));
+        test_serde(&Coding::from_iter(BitsPerFragment(3), &all_bytes));
+        let code = Code { content: 0b101, len: 3 };
+        assert_eq!(serde_json::from_str::<Code>(&serde_json::to_string(&code).unwrap()).unwrap(), code);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn coding_serde_malformed() {
+        let error = |json| serde_json::from_str::<Coding<u8>>(json).err().map(|e| e.to_string());
+        assert!(error(r#"{"values":[97,98,99],"internal_nodes_count":[1,0],"degree":1}"#).is_none());
+        assert!(error(r#"{"values":[97,98,99],"internal_nodes_count":[1,0],"degree":0}"#).unwrap().contains("BitsPerFragment"));
+        assert!(error(r#"{"values":[97,98,99],"internal_nodes_count":[1,0],"degree":32}"#).unwrap().contains("BitsPerFragment"));
+        assert!(error(r#"{"values":[97,98,99],"internal_nodes_count":[],"degree":1}"#).unwrap().contains("do not sum up"));
+        assert!(error(r#"{"values":[97,98,99],"internal_nodes_count":[1,1],"degree":1}"#).unwrap().contains("inconsistent"));
+        assert!(error(r#"{"values":[97,98,99],"internal_nodes_count":[0,1,0],"degree":1}"#).unwrap().contains("more values than nodes"));
+        assert!(error(r#"{"values":[97,98,99],"internal_nodes_count":[3,0],"degree":1}"#).unwrap().contains("inconsistent"));
+        assert!(error(r#"{"values":[97,98,99,100],"internal_nodes_count":[1,0],"degree":1}"#).unwrap().contains("more values than nodes"));
+        assert!(error(r#"{"values":[97,98],"internal_nodes_count":[1,1,0],"degree":1}"#).unwrap().contains("last level of the tree must have values"));
+        assert!(error(r#"{"values":[97],"internal_nodes_count":[1,0],"degree":1}"#).unwrap().contains("one level"));
+        assert!(serde_json::from_str::<Coding<u8, Degree>>(r#"{"values":[97],"internal_nodes_count":[0],"degree":1}"#).is_err());
+    }
+
+    /// Returns the total number of fragments needed to encode values whose number of occurrences is given by `frequencies`.
+    fn encoded_fragments<FS: TreeDegree>(huffman: &Coding<u32, FS>, frequencies: &HashMap<u32, u64>) -> u64 {
+        huffman.codes().map(|(v, c)| frequencies[v] * c.len as u64).sum()
+    }
+
+    fn test_limited<FS: TreeDegree>(degree: FS, frequencies: &HashMap<u32, u64>, max_len: u32) -> Coding<u32, FS> {
+        let unlimited = Coding::from_frequencies_cloned(degree, frequencies);
+        let limited = Coding::from_frequencies_limited(degree, frequencies.clone(), max_len);
+        assert_eq!(limited.values.len(), frequencies.len());
+        assert!(limited.codes().all(|(_, c)| c.len <= max_len));
+        assert!(limited.is_prefix_free());
+        assert!(encoded_fragments(&unlimited, frequencies) <= encoded_fragments(&limited, frequencies));
+        if unlimited.levels().len() as u32 <= max_len {
+            assert_eq!(encoded_fragments(&unlimited, frequencies), encoded_fragments(&limited, frequencies));
+        }
+        limited
+    }
+
+    #[test]
+    fn coding_limited_5sym() {
+        // Huffman lengths are: 4, 4, 3, 2, 1
+        let frequencies: HashMap<u32, u64> = [(0, 1), (1, 1), (2, 2), (3, 4), (4, 8)].into_iter().collect();
+        let limited = test_limited(BitsPerFragment(1), &frequencies, 3);
+        assert_eq!(limited.code_lengths(), [(0, 3), (1, 3), (2, 3), (3, 3), (4, 1)].into_iter().collect());
+        assert_eq!(encoded_fragments(&limited, &frequencies), 32);
+        assert!(limited.is_complete());
+        test_limited(BitsPerFragment(1), &frequencies, 4);
+        test_limited(BitsPerFragment(1), &frequencies, 10);
+        test_limited(Degree(3), &frequencies, 2);
+        test_limited(BitsPerFragment(2), &frequencies, 2);
+        test_limited(BitsPerFragment(1), &[(7, 3)].into_iter().collect(), 0);
+        test_limited(BitsPerFragment(1), &[(7, 3), (8, 5)].into_iter().collect(), 1);
+    }
+
+    #[test]
+    fn coding_limited_256sym_skewed() {
+        // Fibonacci numbers make the Huffman tree very deep
+        let mut fib = (1u64, 1u64);
+        let frequencies: HashMap<u32, u64> = (0..256u32).map(|v| {
+            if v < 40 { fib = (fib.1, fib.0 + fib.1); }
+            (v, fib.0 + v as u64)
+        }).collect();
+        assert!(Coding::from_frequencies_cloned(BitsPerFragment(1), &frequencies).levels().len() > 15);
+        let limited = test_limited(BitsPerFragment(1), &frequencies, 15);
+        assert!(limited.is_complete());
+        // a code of 8 fragments for each value is feasible, so the optimal one cannot be worse:
+        assert!(encoded_fragments(&limited, &frequencies) <= 8 * frequencies.values().sum::<u64>());
+        test_limited(BitsPerFragment(1), &frequencies, 8);
+        test_limited(BitsPerFragment(1), &frequencies, 9);
+        test_limited(BitsPerFragment(2), &frequencies, 4);
+        test_limited(BitsPerFragment(2), &frequencies, 7);
+        test_limited(Degree(3), &frequencies, 6);
+        test_limited(Degree(3), &frequencies, 9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn coding_limited_infeasible() {
+        let frequencies: HashMap<u32, u64> = (0..9).map(|v| (v, v as u64 + 1)).collect();
+        Coding::from_frequencies_limited(BitsPerFragment(1), frequencies, 3);
+    }
+
+    #[test]
+    fn coding_1sym() {
+        let huffman = Coding::from_frequencies_cloned(BitsPerFragment(1), &hashmap!('a' => 100u32));
+        assert_eq!(huffman.total_fragments_count(), 0);
+        assert_eq!(huffman.values.as_ref(), ['a']);
+        assert_eq!(huffman.internal_nodes_count.as_ref(), [0]);
+        assert_eq!(huffman.codes_for_values(), hashmap!('a' => Code{ content: 0, len: 0 }));
+        assert_eq!(huffman.reversed_codes_for_values(), hashmap!('a' => Code{ content: 0, len: 0 }));
+        let mut decoder = huffman.decoder();
+        assert_eq!(
+            decoder.decode(&huffman, &mut std::iter::empty::<u32>()),
+            DecodingResult::Value(&'a')
+        );
+        assert_eq!(decoder.consumed_fragments(), 0);
+        assert!(huffman.is_degenerate());
+        // fragment by fragment decoding of a stream: "a", "b", "a", "a", "b", where "a" is 0 and "b" is 1
+        let two_values = Coding::from_frequencies_cloned(BitsPerFragment(1), &hashmap!('a' => 2u32, 'b' => 1));
+        assert!(!two_values.is_degenerate());
+        assert_eq!(two_values.internal_nodes_count.as_ref(), [0]);
+        for (coding, fragments, expected) in [(&huffman, &[][..], "aaa"), (&two_values, &[0, 1, 0, 0, 1][..], "abaab")] {
+            let mut decoded = String::new();
+            let mut fragments = fragments.iter();
+            let mut decoder = coding.decoder();
+            while decoded.len() < expected.len() {
+                if coding.is_degenerate() {
+                    decoded.push(coding.values[0]);
+                    continue;
+                }
+                if let DecodingResult::Value(v) = decoder.consume(coding, *fragments.next().unwrap()) {
+                    decoded.push(*v);
+                    decoder.reset(coding.degree.as_u32());
+                }
+            }
+            assert_eq!(decoded, expected);
+            assert_eq!(fragments.next(), None);
+        }
+        assert_eq!(huffman.codes().len(), 1);
+        assert_eq!(huffman.levels().len(), 1);
+        assert_eq!(
+            huffman
+                .levels()
+                .map(|(v, _, fragments)| (v.len(), fragments))
+                .collect::<Vec<_>>(),
+            &[(1, 0)]
+        );
+        test_read_write(&huffman);
+        let huffman = Coding::from_frequencies(Degree(3), hashmap!('a' => 1u32));
+        assert_eq!(huffman.codes_for_values(), hashmap!('a' => Code{ content: 0, len: 0 }));
+        assert_eq!(huffman.reversed_codes_for_values(), hashmap!('a' => Code{ content: 0, len: 0 }));
+        assert_eq!(
+            huffman.decoder().decode_vec::<u32>(&huffman, &[]),
+            DecodingResult::Value(&'a')
+        );
+    }
+
+    #[test]
+    fn coding_prefix_free_and_complete() {
+        let frequencies =
+            hashmap!('d' => 12u32, 'e' => 11, 'f' => 10, 'a' => 3, 'b' => 2, 'c' => 1);
+        let huffman = Coding::from_frequencies_cloned(BitsPerFragment(1), &frequencies);
+        assert!(huffman.is_prefix_free());
+        assert!(huffman.is_complete());
+        let huffman = Coding::from_frequencies_cloned(BitsPerFragment(2), &frequencies);
+        assert!(huffman.is_prefix_free());
+        assert!(!huffman.is_complete());    // 3+3 of 4+4 leaves are used
+        let huffman = Coding::from_frequencies_cloned(Degree(3), &frequencies);
+        assert!(huffman.is_prefix_free());
+        let huffman = Coding::from_frequencies(BitsPerFragment(1), hashmap!('a' => 1u32));
+        assert!(huffman.is_prefix_free());
+        assert!(huffman.is_complete());
+        // custom-built coding: a, b, c -> 00, 01, 10 (11 is unused)
+        let huffman = Coding {
+            values: Box::new(['a', 'b', 'c']),
+            internal_nodes_count: Box::new([2, 0]),
+            degree: BitsPerFragment(1),
+        };
+        assert!(huffman.is_prefix_free());
+        assert!(!huffman.is_complete());
+        // custom-built deep chain: one of the two indices at depth 31 is unused
+        let mut huffman = Coding {
+            values: (0..31).collect::<Box<[u32]>>(),
+            internal_nodes_count: std::iter::repeat_n(1, 30).chain(std::iter::once(0)).collect(),
+            degree: BitsPerFragment(1),
+        };
+        assert!(huffman.is_prefix_free());
+        assert!(!huffman.is_complete());
+        huffman.values = (0..32).collect();
+        assert!(huffman.is_complete());
+        let huffman = Coding {
+            values: (0..2*20+1).collect::<Box<[u32]>>(),
+            internal_nodes_count: std::iter::repeat_n(1, 20).chain(std::iter::once(0)).collect(),
+            degree: Degree(3),
+        };
+        assert!(!huffman.is_complete());
+    }
+
     #[test]
     fn coding_3sym_1bit() {
         //  /  \